@@ -0,0 +1,949 @@
+//! # Ellers_rs
+//! An implementation of Eller's maze generation algorithm.
+//!
+//! ## Algorithm
+//! ### Initialization
+//! 1) Create empty row
+//! 2) Add cells to their own unique sets
+//! 3) From left to right randomly add left/right walls
+//!    If we choose not to add a wall, union the sets to which the current cell and
+//!    cell to the right are members
+//! 4) Create bottom walls moving left to right randomly choose to add a wall
+//!    Each set must have at least one cell without a bottom wall
+//!
+//! ### Generating the next row
+//! 1) Copy Previous row to next_row
+//! 2) remove right walls.
+//! 3) if cell.walls.contains(Wall::Bottom) set_id = 0;
+//! 4) remove bottom walls
+//! 5) cells without a set get their own unique set
+//! 6) randomly add right walls, merging sets when not adding a wall
+//!    If two adjacent cells are in the same set, we must add a wall
+//! 7) randomly add bottom walls, each set must have at least one cell without a bottom wall
+//!
+//! ### Completing the maze
+//! 1) create a normal row, except each cell has a bottom wall
+//! 2) remove walls between cells that are members of different sets
+//!    union sets until all cells are members of the same set.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+#[derive(Hash, PartialEq, Eq, Debug, Clone)]
+enum Wall {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+#[derive(Debug, Clone)]
+struct Cell {
+    walls: HashSet<Wall>,
+    label: usize,
+    set_id: usize,
+}
+
+/// A single tile in a `TileGrid`. A maze's walls and passages each get their
+/// own tile, so a `Cell` occupies a 2x2 neighborhood of tiles plus its shared
+/// edges with neighboring cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Wall,
+    Floor,
+}
+
+/// A maze as a real occupancy grid: `Tile::Floor` where you can walk,
+/// `Tile::Wall` where you can't. Resolution is `(2*width+1) x (2*height+1)`
+/// so every wall segment between cells is representable as its own tile.
+pub type TileGrid = Vec<Vec<Tile>>;
+
+/// The full maze, retained row by row as `MazeBuilder` finishes generating
+/// each one. Rows run top to bottom, cells within a row left to right.
+#[derive(Debug, Clone)]
+pub struct Maze {
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Maze {
+    fn new() -> Maze {
+        Maze { rows: Vec::new() }
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows.first().map_or(0, |row| row.len())
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn push_row(&mut self, row: Vec<Cell>) {
+        self.rows.push(row);
+    }
+
+    /// Cells reachable from `pos` in a single step, i.e. adjacent cells that
+    /// don't have a wall between them.
+    fn neighbors(&self, pos: (usize, usize)) -> Vec<(usize, usize)> {
+        let (r, c) = pos;
+        let cell = &self.rows[r][c];
+        let mut neighbors = Vec::new();
+
+        if c + 1 < self.width() {
+            let right = &self.rows[r][c + 1];
+            if !cell.walls.contains(&Wall::Right) && !right.walls.contains(&Wall::Left) {
+                neighbors.push((r, c + 1));
+            }
+        }
+        if c > 0 {
+            let left = &self.rows[r][c - 1];
+            if !cell.walls.contains(&Wall::Left) && !left.walls.contains(&Wall::Right) {
+                neighbors.push((r, c - 1));
+            }
+        }
+        if r + 1 < self.rows.len() {
+            let below = &self.rows[r + 1][c];
+            if !cell.walls.contains(&Wall::Bottom) && !below.walls.contains(&Wall::Top) {
+                neighbors.push((r + 1, c));
+            }
+        }
+        if r > 0 {
+            let above = &self.rows[r - 1][c];
+            if !cell.walls.contains(&Wall::Top) && !above.walls.contains(&Wall::Bottom) {
+                neighbors.push((r - 1, c));
+            }
+        }
+
+        neighbors
+    }
+
+    /// Breadth-first search for the shortest path between two cells,
+    /// returning the cells visited from `start` to `end` inclusive.
+    pub fn solve(&self, start: (usize, usize), end: (usize, usize)) -> Option<Vec<(usize, usize)>> {
+        let mut visited: HashSet<(usize, usize)> = HashSet::new();
+        let mut came_from: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
+
+        visited.insert(start);
+        queue.push_back(start);
+
+        while let Some(pos) = queue.pop_front() {
+            if pos == end {
+                let mut path = vec![end];
+                let mut current = end;
+                while current != start {
+                    current = came_from[&current];
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for next in self.neighbors(pos) {
+                if visited.insert(next) {
+                    came_from.insert(next, pos);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn print(&self, path: &[(usize, usize)]) {
+        print!("{}", self.render(path));
+    }
+
+    /// Distance in steps from `start` to every cell reachable from it,
+    /// via a uniform-weight flood fill over the same adjacency `solve` uses.
+    pub fn distances_from(&self, start: (usize, usize)) -> HashMap<(usize, usize), usize> {
+        let mut distances = HashMap::new();
+        let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
+
+        distances.insert(start, 0);
+        queue.push_back(start);
+
+        while let Some(pos) = queue.pop_front() {
+            let dist = distances[&pos];
+            for next in self.neighbors(pos) {
+                if let std::collections::hash_map::Entry::Vacant(e) = distances.entry(next) {
+                    e.insert(dist + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// The farthest cell from `start`. Ties (multiple cells at the same
+    /// max distance) are broken by row then column, so the result is
+    /// independent of `HashMap`'s randomized iteration order and stable
+    /// across runs for golden-file testing.
+    fn farthest_from(&self, start: (usize, usize)) -> (usize, usize) {
+        self.distances_from(start)
+            .into_iter()
+            .max_by_key(|(pos, dist)| (*dist, std::cmp::Reverse(*pos)))
+            .map(|(pos, _)| pos)
+            .unwrap_or(start)
+    }
+
+    /// The two cells that maximize corridor distance between them: the
+    /// diameter of the maze's spanning tree, and the hardest entrance/exit
+    /// placement. Found by flood-filling twice, each time from the cell
+    /// farthest from the previous fill.
+    pub fn farthest_pair(&self) -> ((usize, usize), (usize, usize)) {
+        let a = self.farthest_from((0, 0));
+        let b = self.farthest_from(a);
+        (a, b)
+    }
+
+    /// Renders the whole maze the way `MazeBuilder::render_row` renders a
+    /// single row, overlaying `path` as `*` in each visited cell's interior.
+    fn render(&self, path: &[(usize, usize)]) -> String {
+        let path: HashSet<(usize, usize)> = path.iter().cloned().collect();
+        let mut out = String::new();
+
+        for (r, row) in self.rows.iter().enumerate() {
+            let mut ceil = String::new();
+            let mut floor = String::new();
+            let mut vertical = String::new();
+
+            for (c, cell) in row.iter().enumerate() {
+                if cell.walls.contains(&Wall::Top) {
+                    ceil.push_str("---");
+                } else {
+                    ceil.push_str("   ");
+                }
+
+                if cell.walls.contains(&Wall::Bottom) {
+                    floor.push_str("---");
+                } else {
+                    floor.push_str("   ");
+                }
+
+                let interior = if path.contains(&(r, c)) { '*' } else { ' ' };
+                if cell.walls.contains(&Wall::Left) && cell.walls.contains(&Wall::Right) {
+                    vertical.push('|');
+                    vertical.push(interior);
+                    vertical.push('|');
+                } else if cell.walls.contains(&Wall::Right) {
+                    vertical.push(' ');
+                    vertical.push(interior);
+                    vertical.push('|');
+                } else if cell.walls.contains(&Wall::Left) {
+                    vertical.push('|');
+                    vertical.push(interior);
+                    vertical.push(' ');
+                } else {
+                    vertical.push(' ');
+                    vertical.push(interior);
+                    vertical.push(' ');
+                }
+
+                vertical.push(' ');
+                ceil.push(' ');
+                floor.push(' ');
+            }
+
+            out.push_str(&format!("{}\n{}\n{}\n", ceil, vertical, floor));
+        }
+
+        out
+    }
+
+    /// Converts the maze into a `TileGrid`: cell centers become `Tile::Floor`,
+    /// and each wall segment is toggled to `Tile::Floor` wherever the
+    /// corresponding `Cell` lacks that `Wall`. Corner tiles (pillars between
+    /// up to four cells) are always `Tile::Wall`.
+    fn to_tile_grid(&self) -> TileGrid {
+        let grid_height = 2 * self.height() + 1;
+        let grid_width = 2 * self.width() + 1;
+        let mut grid = vec![vec![Tile::Wall; grid_width]; grid_height];
+
+        for (r, row) in self.rows.iter().enumerate() {
+            for (c, cell) in row.iter().enumerate() {
+                let tile_row = 2 * r + 1;
+                let tile_col = 2 * c + 1;
+                grid[tile_row][tile_col] = Tile::Floor;
+
+                if !cell.walls.contains(&Wall::Top) {
+                    grid[tile_row - 1][tile_col] = Tile::Floor;
+                }
+                if !cell.walls.contains(&Wall::Bottom) {
+                    grid[tile_row + 1][tile_col] = Tile::Floor;
+                }
+                if !cell.walls.contains(&Wall::Left) {
+                    grid[tile_row][tile_col - 1] = Tile::Floor;
+                }
+                if !cell.walls.contains(&Wall::Right) {
+                    grid[tile_row][tile_col + 1] = Tile::Floor;
+                }
+            }
+        }
+
+        grid
+    }
+}
+
+#[derive(Debug)]
+pub struct MazeBuilder {
+    sets: HashMap<usize, HashSet<usize>>,
+    cells: HashMap<usize, Cell>,
+    width: usize,
+    set_cnt: usize,
+    label_cnt: usize,
+    row: Vec<usize>,
+    rng: StdRng,
+    maze: Maze,
+    record_history: bool,
+    history: Vec<TileGrid>,
+    total_rows: Option<usize>,
+}
+
+impl MazeBuilder {
+    /// Eller's algorithm:
+    /// Copy Previous row to next_row
+    /// 1) remove right walls.
+    /// 2) if cell.walls.contains(Wall::Bottom) set_id = 0;
+    /// 3) remove bottom walls
+    /// 4) cells without a set get their own unique set
+    /// 5) randomly add right walls, merging sets as appropriate (when not adding a wall)
+    ///    If two adjacent cells are in the same set, we must add a wall
+    /// 6) randomly add bottom walls, each set must have a down-passage
+
+    /// Completing the maze.
+    /// 1) create a normal row, except each cell has a bottom wall
+    /// 2) remove walls between cells that are members of different sets
+    ///    union sets until all cells are members of the same set.
+    ///
+    /// Returns a vector of cell labels.
+    pub fn ellers(&mut self) -> &Vec<usize> {
+        let row = &mut self.row;
+        let mut new_row = row.clone();
+
+        for i in 0..new_row.len() {
+            // Clone cell "above" new_row cell.
+            new_row[i] = self.label_cnt;
+            let mut new_cell = self.cells.get(&row[i]).unwrap().clone();
+            new_cell.label = self.label_cnt;
+
+            let set = self.sets.entry(new_cell.set_id).or_insert(HashSet::new());
+            set.insert(self.label_cnt);
+            self.cells.insert(new_cell.label, new_cell);
+
+            if let Some(cell) = self.cells.get_mut(&self.label_cnt) {
+                cell.walls.remove(&Wall::Top);
+                cell.walls.remove(&Wall::Right);
+                cell.walls.remove(&Wall::Left);
+
+                if cell.walls.remove(&Wall::Bottom) {
+                    cell.walls.insert(Wall::Top);
+
+                    let old_set = self.sets.entry(cell.set_id).or_insert(HashSet::new());
+                    old_set.remove(&cell.label);
+
+                    cell.set_id = self.set_cnt;
+                    self.set_cnt += 1;
+
+                    let set = self.sets.entry(cell.set_id).or_insert(HashSet::new());
+                    set.insert(cell.label);
+                }
+            }
+
+            self.label_cnt += 1;
+        }
+
+        let mut iter = new_row.iter().peekable();
+        while let Some(i) = iter.next() {
+            let cells = &mut self.cells;
+            let sets = &mut self.sets;
+
+            let mut merge: bool = false;
+            let mut add_left: bool = false;
+            let mut next_set = 0;
+
+            // Get next set
+            if let Some(next_label) = iter.peek() {
+                if let Some(next_cell) = cells.get_mut(next_label) {
+                    next_set = next_cell.set_id;
+                }
+            }
+
+            if let Some(cell) = cells.get_mut(&i) {
+                if next_set != 0 && next_set == cell.set_id {
+                    cell.walls.insert(Wall::Right);
+                    add_left = true;
+                } else if self.rng.gen::<bool>() {
+                    cell.walls.insert(Wall::Right);
+                    add_left = true;
+                } else {
+                    merge = true;
+                }
+            }
+
+            if let Some(next_label) = iter.peek() {
+                let current_set_id = match cells.get(&i) {
+                    Some(cell) => cell.set_id,
+                    None => 0,
+                };
+
+                // Use flags to avoid two mutable references.
+                if merge && current_set_id != 0 {
+                    let next_cell = cells.get_mut(&next_label).unwrap();
+                    if let Some(old_set) = sets.get_mut(&next_cell.set_id) {
+                        old_set.remove(&next_cell.label);
+                    }
+                    next_cell.set_id = current_set_id;
+                    if let Some(new_set) = sets.get_mut(&current_set_id) {
+                        new_set.insert(next_cell.label);
+                    }
+                }
+
+                if add_left {
+                    if let Some(cell) = cells.get_mut(&next_label) {
+                        cell.walls.insert(Wall::Left);
+                    }
+                }
+            }
+        }
+
+        // Make sure outside edges have walls.
+        if let Some(cell) = self.cells.get_mut(&new_row[0]) {
+            cell.walls.insert(Wall::Left);
+        }
+        if let Some(cell) = self.cells.get_mut(&new_row[new_row.len() - 1]) {
+            cell.walls.insert(Wall::Right);
+        }
+
+        // Snapshot the finished row into the maze before dropping it from the
+        // working set, so the full grid survives for solving/rendering.
+        let finished_row: Vec<Cell> = self
+            .row
+            .iter()
+            .map(|i| self.cells.get(i).unwrap().clone())
+            .collect();
+        self.maze.push_row(finished_row);
+        if self.record_history {
+            self.record_snapshot();
+        }
+
+        // Remove cells and their labels from sets before the row they are in is dropped.
+        for i in &self.row {
+            let mut set_id = 0;
+            if let Some(cell) = self.cells.remove(i) {
+                set_id = cell.set_id;
+            }
+
+            if set_id != 0 {
+                if let Some(set) = self.sets.get_mut(&set_id) {
+                    set.remove(i);
+                }
+            }
+        }
+
+        self.row = new_row;
+        self.init_bottom_walls();
+        &self.row
+    }
+
+    pub fn end(&mut self) {
+        for i in &self.row {
+            if let Some(cell) = self.cells.get_mut(i) {
+                cell.walls.insert(Wall::Bottom);
+            }
+        }
+
+        let mut iter = self.row.iter().peekable();
+        while let Some(label) = iter.next() {
+            let mut set_id = 0;
+            if let Some(next_label) = iter.peek() {
+                if let Some(next_cell) = self.cells.get(next_label) {
+                    set_id = next_cell.set_id;
+                }
+            }
+
+            let mut union = true;
+            let mut target_set = 0;
+            if set_id != 0 {
+                if let Some(cell) = self.cells.get_mut(label) {
+                    if set_id != cell.set_id {
+                        union = true;
+                        target_set = cell.set_id;
+                        cell.walls.remove(&Wall::Right);
+                    }
+                }
+            }
+
+            if union {
+                if let Some(next_label) = iter.peek() {
+                    if let Some(next_cell) = self.cells.get_mut(next_label) {
+                        next_cell.walls.remove(&Wall::Left);
+                    }
+                }
+
+                let mut source = Vec::new();
+                if let Some(source_set) = self.sets.get(&set_id) {
+                    for i in source_set {
+                        source.push(*i);
+                    }
+                }
+                if let Some(target_set) = self.sets.get_mut(&target_set) {
+                    for i in source {
+                        target_set.insert(i);
+                    }
+                }
+            }
+        }
+
+        let finished_row: Vec<Cell> = self
+            .row
+            .iter()
+            .map(|i| self.cells.get(i).unwrap().clone())
+            .collect();
+        self.maze.push_row(finished_row);
+        if self.record_history {
+            self.record_snapshot();
+        }
+    }
+
+    /// Opts into recording a `TileGrid` snapshot after every generated row,
+    /// for callers that want to replay construction frame by frame. `rows`
+    /// is the total number of rows the maze will have once finished (the
+    /// same count passed to `build`, or the number of `ellers` calls plus
+    /// one the caller plans to drive directly), so every snapshot can be
+    /// padded to one fixed canvas up front instead of growing frame by
+    /// frame as rows are generated.
+    pub fn with_history(mut self, rows: usize) -> MazeBuilder {
+        self.record_history = true;
+        self.total_rows = Some(rows);
+        self
+    }
+
+    /// Snapshots recorded so far, oldest first. Empty unless `with_history`
+    /// was used.
+    pub fn history(&self) -> &[TileGrid] {
+        &self.history
+    }
+
+    /// Renders the maze-so-far as a `TileGrid`, padded with solid-wall rows
+    /// up to `total_rows` so every frame in `history` shares the same canvas
+    /// size.
+    fn record_snapshot(&mut self) {
+        let total_rows = self
+            .total_rows
+            .unwrap_or_else(|| self.maze.height())
+            .max(self.maze.height());
+        let grid_width = 2 * self.width + 1;
+        let mut grid = vec![vec![Tile::Wall; grid_width]; 2 * total_rows + 1];
+
+        for (r, row) in self.maze.to_tile_grid().into_iter().enumerate() {
+            grid[r] = row;
+        }
+
+        self.history.push(grid);
+    }
+
+    /// The full, generated maze: every row produced so far, oldest first.
+    pub fn maze(&self) -> &Maze {
+        &self.maze
+    }
+
+    /// Generates `rows` rows of maze, finishes it, and returns the result as
+    /// a `TileGrid` at resolution `(2*width+1) x (2*rows+1)` so downstream
+    /// code can consume it without scraping printed text. The finishing row
+    /// is always generated, so `rows` of 0 gives the same one-row maze as 1.
+    pub fn build(&mut self, rows: usize) -> TileGrid {
+        self.total_rows = Some(rows);
+        for _ in 0..rows.saturating_sub(1) {
+            self.ellers();
+        }
+        self.end();
+        self.maze.to_tile_grid()
+    }
+
+    pub fn new(width: usize) -> MazeBuilder {
+        MazeBuilder::from_rng(width, StdRng::from_entropy())
+    }
+
+    /// Builds a maze with a seeded `StdRng`, making generation reproducible:
+    /// two builders created with the same seed produce byte-identical mazes.
+    pub fn with_seed(width: usize, seed: u64) -> MazeBuilder {
+        MazeBuilder::from_rng(width, StdRng::seed_from_u64(seed))
+    }
+
+    fn from_rng(width: usize, rng: StdRng) -> MazeBuilder {
+        let mut maze_bldr = MazeBuilder {
+            sets: HashMap::new(),
+            cells: HashMap::new(),
+            width: width,
+            set_cnt: 1,
+            label_cnt: 0,
+            row: Vec::new(),
+            rng: rng,
+            maze: Maze::new(),
+            record_history: false,
+            history: Vec::new(),
+            total_rows: None,
+        };
+
+        // Generate the initial row and put each cell into it's own set.
+        while maze_bldr.label_cnt < maze_bldr.width {
+            maze_bldr.cells.insert(
+                maze_bldr.label_cnt,
+                Cell {
+                    walls: HashSet::new(),
+                    label: maze_bldr.label_cnt,
+                    set_id: maze_bldr.set_cnt,
+                },
+            );
+
+            maze_bldr.row.push(maze_bldr.label_cnt);
+            maze_bldr
+                .cells
+                .get_mut(&maze_bldr.label_cnt)
+                .unwrap()
+                .walls
+                .insert(Wall::Top);
+            let set = maze_bldr
+                .sets
+                .entry(maze_bldr.set_cnt)
+                .or_insert(HashSet::new());
+            set.insert(maze_bldr.label_cnt);
+
+            maze_bldr.label_cnt += 1;
+            maze_bldr.set_cnt += 1;
+        }
+
+        maze_bldr
+            .cells
+            .get_mut(&0)
+            .unwrap()
+            .walls
+            .insert(Wall::Left);
+        maze_bldr
+            .cells
+            .get_mut(&(width - 1))
+            .unwrap()
+            .walls
+            .insert(Wall::Right);
+
+        maze_bldr.init_vertical_walls();
+        maze_bldr.init_bottom_walls();
+
+        maze_bldr
+    }
+
+    fn init_bottom_walls(&mut self) {
+        for x in 1..self.width - 1 {
+            if self.rng.gen::<bool>() {
+                let label = self.row[x];
+                if let Some(cell) = self.cells.get_mut(&label) {
+                    cell.walls.insert(Wall::Bottom);
+                }
+            }
+        }
+        for x in 1..self.width - 1 {
+            let label = self.row[x];
+            let set_label = self.cells.get(&label).unwrap().set_id;
+            if let Some(set) = self.sets.get(&set_label) {
+                let mut has_down_passage = false;
+                for cell_label in set {
+                    if !self
+                        .cells
+                        .get(&cell_label)
+                        .unwrap()
+                        .walls
+                        .contains(&Wall::Bottom)
+                    {
+                        has_down_passage = true;
+                        break;
+                    }
+                }
+                if !has_down_passage {
+                    if let Some(cell) = self.cells.get_mut(&label) {
+                        cell.walls.remove(&Wall::Bottom);
+                    }
+                }
+            }
+        }
+    }
+
+    fn init_vertical_walls(&mut self) {
+        for x in 0..self.width - 1 {
+            if self.rng.gen::<bool>() {
+                let current_label = self.row[x];
+                let next_label = self.row[x + 1];
+                if let Some(cell) = self.cells.get_mut(&current_label) {
+                    cell.walls.insert(Wall::Right);
+                }
+                if let Some(cell) = self.cells.get_mut(&next_label) {
+                    cell.walls.insert(Wall::Left);
+                }
+            } else {
+                let l1 = self.row[x];
+                let l2 = self.row[x + 1];
+                let target_set: usize = self.cells.get(&l1).unwrap().set_id;
+                let l2_cell = self.cells.get_mut(&l2).unwrap();
+                l2_cell.set_id = target_set;
+
+                if let Some(set) = self.sets.get_mut(&target_set) {
+                    set.insert(l2);
+                }
+                if let Some(cell) = self.cells.get(&l2) {
+                    // Remove l2 from previous set
+                    if let Some(set) = self.sets.get_mut(&cell.set_id) {
+                        set.remove(&l2);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn print_row(&self) {
+        print!("{}", self.render_row());
+    }
+
+    /// Renders the current row the same way `print_row` does, without
+    /// printing it. Lets callers (and tests) compare generated mazes
+    /// byte-for-byte.
+    fn render_row(&self) -> String {
+        let mut ceil = String::new();
+        let mut floor = String::new();
+        let mut vertical = String::new();
+
+        for label in self.row.iter() {
+            if let Some(cell) = self.cells.get(&label) {
+                if cell.walls.contains(&Wall::Top) {
+                    ceil.push('-');
+                    ceil.push('-');
+                    ceil.push('-');
+                } else {
+                    ceil.push(' ');
+                    ceil.push(' ');
+                    ceil.push(' ');
+                }
+
+                if cell.walls.contains(&Wall::Bottom) {
+                    floor.push('-');
+                    floor.push('-');
+                    floor.push('-');
+                } else {
+                    floor.push(' ');
+                    floor.push(' ');
+                    floor.push(' ');
+                }
+
+                if cell.walls.contains(&Wall::Left) && cell.walls.contains(&Wall::Right) {
+                    vertical.push('|');
+                    vertical.push_str(&cell.set_id.to_string());
+                    vertical.push('|');
+                } else if cell.walls.contains(&Wall::Right) {
+                    vertical.push(' ');
+                    vertical.push_str(&cell.set_id.to_string());
+                    vertical.push('|');
+                } else if cell.walls.contains(&Wall::Left) {
+                    vertical.push('|');
+                    vertical.push_str(&cell.set_id.to_string());
+                    vertical.push(' ');
+                } else {
+                    vertical.push(' ');
+                    vertical.push_str(&cell.set_id.to_string());
+                    vertical.push(' ');
+                }
+            }
+
+            vertical.push(' ');
+            ceil.push(' ');
+            floor.push(' ');
+        }
+
+        format!("{}\n{}\n{}\n", ceil, vertical, floor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIDTH: usize = 10;
+
+    #[test]
+    fn new_maze_test() {
+        let maze = MazeBuilder::new(WIDTH);
+        assert_eq!(WIDTH + 1, maze.set_cnt);
+        assert_eq!(WIDTH, maze.label_cnt);
+        assert_eq!(WIDTH, maze.row.len());
+
+        // Initial row
+        assert!(maze.cells[&0].walls.contains(&Wall::Left));
+        assert!(maze.cells[&0].walls.contains(&Wall::Top));
+
+        for i in 1..WIDTH {
+            assert!(maze.cells[&i].walls.contains(&Wall::Top));
+        }
+
+        assert!(maze.cells[&(WIDTH - 1)].walls.contains(&Wall::Top));
+        assert!(maze.cells[&(WIDTH - 1)].walls.contains(&Wall::Right));
+    }
+
+    #[test]
+    fn test_copy_row() {
+        let maze_bldr = &mut MazeBuilder::new(WIDTH);
+        let fst_row = maze_bldr.row.clone();
+        let snd_row = maze_bldr.ellers();
+
+        // `ellers` copies the previous row into a new one with fresh labels,
+        // so the old labels are gone from `cells` by the time it returns;
+        // only the row length is preserved from the caller's point of view.
+        assert_eq!(fst_row.len(), snd_row.len());
+    }
+
+    #[test]
+    fn same_seed_is_reproducible() {
+        let mut a = MazeBuilder::with_seed(WIDTH, 1234);
+        let mut b = MazeBuilder::with_seed(WIDTH, 1234);
+
+        for _ in 0..4 {
+            a.ellers();
+            b.ellers();
+            assert_eq!(a.render_row(), b.render_row());
+        }
+
+        a.end();
+        b.end();
+        assert_eq!(a.render_row(), b.render_row());
+    }
+
+    #[test]
+    fn different_seeds_diverge() {
+        let mut a = MazeBuilder::with_seed(WIDTH, 1);
+        let mut b = MazeBuilder::with_seed(WIDTH, 2);
+
+        let mut diverged = a.render_row() != b.render_row();
+        for _ in 0..4 {
+            a.ellers();
+            b.ellers();
+            diverged |= a.render_row() != b.render_row();
+        }
+
+        assert!(diverged);
+    }
+
+    #[test]
+    fn solve_finds_path_between_corners() {
+        let mut builder = MazeBuilder::with_seed(WIDTH, 42);
+        for _ in 0..3 {
+            builder.ellers();
+        }
+        builder.end();
+
+        let maze = builder.maze();
+        let entrance = (0, 0);
+        let exit = (maze.height() - 1, maze.width() - 1);
+        let path = maze.solve(entrance, exit).expect("maze should be fully connected");
+
+        assert_eq!(path[0], entrance);
+        assert_eq!(path[path.len() - 1], exit);
+        for pair in path.windows(2) {
+            assert!(maze.neighbors(pair[0]).contains(&pair[1]));
+        }
+    }
+
+    #[test]
+    fn farthest_pair_is_reachable_and_maximal() {
+        let mut builder = MazeBuilder::with_seed(WIDTH, 42);
+        for _ in 0..3 {
+            builder.ellers();
+        }
+        builder.end();
+
+        let maze = builder.maze();
+        let (a, b) = maze.farthest_pair();
+        let distances = maze.distances_from(a);
+
+        // b must be (one of) the farthest cells from a, and every other
+        // cell's distance from a is no greater.
+        assert_eq!(distances[&b], *distances.values().max().unwrap());
+        assert!(maze.solve(a, b).is_some());
+    }
+
+    #[test]
+    fn farthest_from_breaks_ties_by_row_then_column() {
+        // A single open row of three cells: (0,0) and (0,2) are tied at
+        // distance 1 from the middle cell. The tie must resolve the same
+        // way every run, not follow HashMap's randomized iteration order.
+        let mut maze = Maze::new();
+        let row: Vec<Cell> = (0..3)
+            .map(|label| Cell {
+                walls: HashSet::new(),
+                label,
+                set_id: 0,
+            })
+            .collect();
+        maze.push_row(row);
+
+        assert_eq!(maze.farthest_from((0, 1)), (0, 0));
+    }
+
+    #[test]
+    fn build_returns_tile_grid_at_expected_resolution() {
+        let rows = 4;
+        let mut builder = MazeBuilder::with_seed(WIDTH, 7);
+        let grid = builder.build(rows);
+
+        assert_eq!(grid.len(), 2 * rows + 1);
+        assert_eq!(grid[0].len(), 2 * WIDTH + 1);
+
+        // Every cell center is floor.
+        for r in 0..rows {
+            for c in 0..WIDTH {
+                assert_eq!(grid[2 * r + 1][2 * c + 1], Tile::Floor);
+            }
+        }
+    }
+
+    #[test]
+    fn build_retains_exactly_the_requested_rows() {
+        let rows = 4;
+        let mut builder = MazeBuilder::with_seed(WIDTH, 7);
+        builder.build(rows);
+
+        assert_eq!(builder.maze().height(), rows);
+    }
+
+    #[test]
+    fn history_is_empty_unless_opted_in() {
+        let mut builder = MazeBuilder::with_seed(WIDTH, 7);
+        builder.build(4);
+        assert!(builder.history().is_empty());
+    }
+
+    #[test]
+    fn history_records_one_frame_per_row_at_a_fixed_resolution() {
+        let rows = 4;
+        let mut builder = MazeBuilder::with_seed(WIDTH, 7).with_history(rows);
+        builder.build(rows);
+
+        assert_eq!(builder.history().len(), rows);
+        for frame in builder.history() {
+            assert_eq!(frame.len(), 2 * rows + 1);
+            assert_eq!(frame[0].len(), 2 * WIDTH + 1);
+        }
+
+        // Later frames have generated strictly more of the maze than earlier ones.
+        let floor_count = |frame: &TileGrid| {
+            frame
+                .iter()
+                .flatten()
+                .filter(|tile| **tile == Tile::Floor)
+                .count()
+        };
+        for pair in builder.history().windows(2) {
+            assert!(floor_count(&pair[0]) < floor_count(&pair[1]));
+        }
+    }
+}